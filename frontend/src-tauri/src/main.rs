@@ -1,27 +1,132 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     error::Error,
-    fs::{create_dir_all, OpenOptions},
+    fs::{create_dir_all, metadata, remove_file, rename, OpenOptions},
     io::Write,
     net::TcpListener,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command as StdCommand,
-    sync::Mutex,
-    time::{Duration, Instant, SystemTime},
+    sync::{
+        atomic::{AtomicBool, AtomicU16, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use tauri::{api::process::{Command as TauriCommand, CommandEvent, Encoding}, Manager, RunEvent};
 use tokio::time::sleep;
 
-struct BackendState(Mutex<Option<tauri::api::process::CommandChild>>);
+// `0` guarda o filho vivo; `1` sinaliza que o encerramento foi intencional
+// para o supervisor nao tentar respawnar um backend que nos mesmos matamos.
+struct BackendState(Mutex<Option<tauri::api::process::CommandChild>>, AtomicBool);
+
+// Porta efetiva onde o sidecar escuta; escolhida dinamicamente a cada spawn e
+// consultavel pelo frontend via comando `backend_port`.
+struct BackendPort(AtomicU16);
+
+/// Ring buffer com as ultimas linhas de evento do backend, exposto ao frontend
+/// via comando `get_recent_logs` para que a diagnose saia do disco e apareca
+/// na UI.
+struct LogRing(Mutex<VecDeque<LogRecord>>);
+
+/// Uma linha estruturada do backend guardada no ring buffer.
+#[derive(Clone, serde::Serialize)]
+struct LogRecord {
+    ts_ms: u64,
+    kind: String,
+    message: String,
+}
+
+const LOG_RING_CAPACITY: usize = 200;
 
 const SIDECAR_NAME: &str = "estoque_backend";
-const SIDECAR_ENV_PORT: &str = "8000";
 const SIDECAR_ENV_APP_PROD: &str = "prod";
 const SIDECAR_ENV_APP_DEV: &str = "dev";
 
+// Parametros do supervisor de respawn do backend.
+const RESTART_BACKOFF_INITIAL: Duration = Duration::from_millis(500);
+const RESTART_BACKOFF_CAP: Duration = Duration::from_secs(30);
+const RESTART_BACKOFF_FACTOR: f32 = 1.5;
+const RESTART_MAX_FAILURES: usize = 5;
+const RESTART_FAILURE_WINDOW: Duration = Duration::from_secs(60);
+
+// Janela de debounce do watcher de desenvolvimento: uma rajada de saves vira
+// um unico restart.
+const DEV_WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// Tempo de espera pelo encerramento gracioso antes do kill forcado.
+const BACKEND_GRACE_DEFAULT: Duration = Duration::from_secs(5);
+
+/// Periodo de graca configuravel via `CHRONOS_KILL_GRACE_MS`.
+fn backend_grace_period() -> Duration {
+    std::env::var("CHRONOS_KILL_GRACE_MS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(BACKEND_GRACE_DEFAULT)
+}
+
+/// Estado do backend emitido ao frontend via evento `backend://status`.
+#[derive(Clone, serde::Serialize)]
+struct BackendStatus {
+    state: &'static str,
+    restarts: u32,
+}
+
+fn emit_backend_status(window: &tauri::Window, state: &'static str, restarts: u32) {
+    let _ = window.emit("backend://status", BackendStatus { state, restarts });
+    log_line(&format!("backend status: {state} (restarts={restarts})"));
+}
+
+/// Registra uma linha no ring buffer compartilhado, descartando a mais antiga
+/// quando a capacidade estoura.
+fn ring_push(app: &tauri::AppHandle, kind: &str, message: &str) {
+    let ts_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let mut ring = app.state::<LogRing>().0.lock().unwrap();
+    if ring.len() >= LOG_RING_CAPACITY {
+        ring.pop_front();
+    }
+    ring.push_back(LogRecord {
+        ts_ms,
+        kind: kind.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// Exibe uma notificacao nativa de desktop para eventos que o usuario final
+/// precisa ver (o `tauri.log` ele nunca abre).
+fn notify_user(summary: &str, body: &str) {
+    if let Err(err) = notify_rust::Notification::new()
+        .summary(summary)
+        .body(body)
+        .show()
+    {
+        log_line(&format!("falha ao exibir notificacao: {err}"));
+    }
+}
+
+fn next_backoff(current: Duration) -> Duration {
+    std::cmp::min(
+        Duration::from_millis((current.as_millis() as f32 * RESTART_BACKOFF_FACTOR) as u64),
+        RESTART_BACKOFF_CAP,
+    )
+}
+
+/// Registra uma falha e devolve `true` enquanto o circuito nao estourou:
+/// mais que `RESTART_MAX_FAILURES` falhas dentro de `RESTART_FAILURE_WINDOW`
+/// abre o disjuntor e o supervisor desiste.
+fn register_failure(failures: &mut Vec<Instant>) -> bool {
+    let now = Instant::now();
+    failures.retain(|t| now.duration_since(*t) < RESTART_FAILURE_WINDOW);
+    failures.push(now);
+    failures.len() <= RESTART_MAX_FAILURES
+}
+
 fn log_path() -> PathBuf {
     if let Ok(base) = std::env::var("LOCALAPPDATA") {
         return PathBuf::from(base).join("ChronosInventory").join("logs").join("tauri.log");
@@ -29,22 +134,127 @@ fn log_path() -> PathBuf {
     std::env::temp_dir().join("chronos_inventory_tauri.log")
 }
 
-fn log_line(message: &str) {
+// Rotacao por tamanho: rola ao atingir 5MB e mantem os 3 ultimos arquivos.
+const LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+const LOG_KEEP: u32 = 3;
+
+// Serializa rotacao+append entre os multiplos escritores de log.
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Um registro estruturado do log da aplicacao.
+#[derive(serde::Serialize)]
+struct LogEntry<'a> {
+    ts: String,
+    level: &'a str,
+    kind: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signal: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<&'a str>,
+}
+
+fn level_rank(level: &str) -> u8 {
+    match level {
+        "trace" => 0,
+        "debug" => 1,
+        "info" => 2,
+        "warn" => 3,
+        "error" => 4,
+        _ => 2,
+    }
+}
+
+/// Nivel minimo emitido, configuravel via `CHRONOS_LOG_LEVEL` (default `info`).
+fn level_enabled(level: &str) -> bool {
+    let min = std::env::var("CHRONOS_LOG_LEVEL").unwrap_or_default();
+    let min = if min.trim().is_empty() { "info" } else { min.trim() };
+    level_rank(level) >= level_rank(&min.to_lowercase())
+}
+
+/// Formato de saida, configuravel via `CHRONOS_LOG_FORMAT` (`json` default ou `text`).
+fn log_is_text() -> bool {
+    std::env::var("CHRONOS_LOG_FORMAT")
+        .map(|v| v.trim().eq_ignore_ascii_case("text"))
+        .unwrap_or(false)
+}
+
+fn rolled_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// Rola `path` para `path.1`, deslocando os arquivos mais antigos e descartando
+/// o que ultrapassar `LOG_KEEP`.
+fn rotate_if_needed(path: &Path) {
+    let len = metadata(path).map(|m| m.len()).unwrap_or(0);
+    if len < LOG_MAX_BYTES {
+        return;
+    }
+    let _ = remove_file(rolled_path(path, LOG_KEEP));
+    for i in (1..LOG_KEEP).rev() {
+        let _ = rename(rolled_path(path, i), rolled_path(path, i + 1));
+    }
+    let _ = rename(path, rolled_path(path, 1));
+}
+
+/// Escreve um registro estruturado (NDJSON por padrao) nos dois caminhos de log,
+/// rotacionando por tamanho. `log_line` e o atalho para mensagens simples.
+fn log_record(
+    level: &str,
+    kind: &str,
+    message: &str,
+    code: Option<i32>,
+    signal: Option<i32>,
+    stream: Option<&str>,
+) {
+    if !level_enabled(level) {
+        return;
+    }
+    let ts = chrono::Utc::now().to_rfc3339();
+    let line = if log_is_text() {
+        format!("{ts} [{level}] {kind} {message}")
+    } else {
+        let entry = LogEntry {
+            ts: ts.clone(),
+            level,
+            kind,
+            message,
+            code,
+            signal,
+            stream,
+        };
+        serde_json::to_string(&entry).unwrap_or_else(|_| format!("{ts} {message}"))
+    };
+
     let paths = [
         log_path(),
         std::env::temp_dir().join("chronos_inventory_tauri.log"),
     ];
 
+    // Serializa rotacao+append: `log_record` e chamado concorrentemente pela
+    // task do supervisor, pela thread do watcher e pela main, e dois escritores
+    // poderiam correr na cadeia de `rename` (e no Windows falhar com o arquivo
+    // aberto por outro). Segura o lock por toda a escrita.
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
     for path in paths {
         if let Some(parent) = path.parent() {
             let _ = create_dir_all(parent);
         }
+        rotate_if_needed(&path);
         if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
-            let _ = writeln!(file, "{:?} {}", SystemTime::now(), message);
+            let _ = writeln!(file, "{line}");
         }
     }
 }
 
+fn log_line(message: &str) {
+    log_record("info", "message", message, None, None, None);
+}
+
 async fn wait_for_health(url: &str, timeout: Duration) -> bool {
     let client = reqwest::Client::new();
     let mut delay = Duration::from_millis(300);
@@ -105,14 +315,21 @@ fn log_startup_paths(app: &tauri::App) {
     }
 }
 
-fn spawn_backend() -> Result<(tauri::api::process::CommandChild, tauri::async_runtime::Receiver<CommandEvent>), Box<dyn Error>> {
+/// Descobre uma porta efemera livre pedindo ao SO (`127.0.0.1:0`). O listener
+/// e liberado ao sair, ficando disponivel para o sidecar assumir.
+fn pick_free_port() -> std::io::Result<u16> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+fn spawn_backend(port: u16) -> Result<(tauri::api::process::CommandChild, tauri::async_runtime::Receiver<CommandEvent>), Box<dyn Error>> {
     let app_env = if cfg!(debug_assertions) {
         SIDECAR_ENV_APP_DEV
     } else {
         SIDECAR_ENV_APP_PROD
     };
     let mut envs = HashMap::from([
-        ("PORT".to_string(), SIDECAR_ENV_PORT.to_string()),
+        ("PORT".to_string(), port.to_string()),
         ("APP_ENV".to_string(), app_env.to_string()),
         // Evita logs em cp1252 que quebram parser UTF-8 do Tauri.
         ("PYTHONUTF8".to_string(), "1".to_string()),
@@ -126,7 +343,7 @@ fn spawn_backend() -> Result<(tauri::api::process::CommandChild, tauri::async_ru
     log_line(&format!(
         "spawn sidecar: name={} PORT={} APP_ENV={} CHRONOS_APP_DIR={}",
         SIDECAR_NAME,
-        SIDECAR_ENV_PORT,
+        port,
         app_env,
         envs.get("CHRONOS_APP_DIR").cloned().unwrap_or_else(|| "(inherit/default)".to_string())
     ));
@@ -138,75 +355,379 @@ fn spawn_backend() -> Result<(tauri::api::process::CommandChild, tauri::async_ru
     Ok((child, rx))
 }
 
-fn stop_backend(app: &tauri::AppHandle, reason: &str) {
+/// Supervisiona o ciclo de vida do sidecar: (re)spawna, acompanha os eventos
+/// no receiver e, quando o backend morre sem pedido explicito de encerramento,
+/// respawna com backoff exponencial ate o disjuntor abrir.
+fn start_backend_supervisor(app: tauri::AppHandle, window: tauri::Window) {
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = RESTART_BACKOFF_INITIAL;
+        let mut restarts: u32 = 0;
+        let mut failures: Vec<Instant> = Vec::new();
+        let mut first_start = true;
+
+        loop {
+            // Encerramento pode ter sido pedido enquanto dormiamos no backoff:
+            // nao inicia um novo backend que ficaria orfao no teardown/restart.
+            if app.state::<BackendState>().1.load(Ordering::SeqCst) {
+                log_line("supervisor: encerramento intencional, nao inicia novo backend");
+                emit_backend_status(&window, "dead", restarts);
+                return;
+            }
+
+            emit_backend_status(&window, "starting", restarts);
+
+            let port = match pick_free_port() {
+                Ok(port) => port,
+                Err(err) => {
+                    log_line(&format!("Falha ao escolher porta livre: {err}"));
+                    // Mesmo sem backend, a UI precisa aparecer para renderizar o erro.
+                    let _ = window.show();
+                    first_start = false;
+                    if !register_failure(&mut failures) {
+                        log_line("supervisor: falhas demais ao iniciar, desistindo");
+                        emit_backend_status(&window, "dead", restarts);
+                        return;
+                    }
+                    restarts += 1;
+                    emit_backend_status(&window, "restarting", restarts);
+                    sleep(backoff).await;
+                    backoff = next_backoff(backoff);
+                    continue;
+                }
+            };
+            app.state::<BackendPort>().0.store(port, Ordering::SeqCst);
+
+            let (child, mut rx) = match spawn_backend(port) {
+                Ok(pair) => pair,
+                Err(err) => {
+                    log_line(&format!("Falha ao iniciar backend: {err}"));
+                    ring_push(&app, "error", &format!("falha ao iniciar backend: {err}"));
+                    notify_user("ChronosInventory", "Backend falhou ao iniciar.");
+                    // Mesmo sem backend, a UI precisa aparecer para renderizar o erro.
+                    let _ = window.show();
+                    first_start = false;
+                    if !register_failure(&mut failures) {
+                        log_line("supervisor: falhas demais ao iniciar, desistindo");
+                        emit_backend_status(&window, "dead", restarts);
+                        return;
+                    }
+                    restarts += 1;
+                    emit_backend_status(&window, "restarting", restarts);
+                    sleep(backoff).await;
+                    backoff = next_backoff(backoff);
+                    continue;
+                }
+            };
+
+            log_line("Backend iniciado");
+            *app.state::<BackendState>().0.lock().unwrap() = Some(child);
+            let spawned_at = Instant::now();
+
+            let health_url = format!("http://127.0.0.1:{port}/health");
+            let healthy = wait_for_health(&health_url, Duration::from_secs(20)).await;
+            if healthy {
+                // Injeta a porta como global para a UI saber onde fica a API.
+                let _ = window.eval(&format!("window.__CHRONOS_BACKEND_PORT__ = {port};"));
+                emit_backend_status(&window, "healthy", restarts);
+            } else {
+                log_line("Backend healthcheck falhou. Mostrando UI mesmo assim.");
+                ring_push(&app, "error", "health check expirou");
+                notify_user("ChronosInventory", "O health check do backend expirou.");
+            }
+            if first_start {
+                let _ = window.show();
+                first_start = false;
+            }
+
+            // Drena eventos ate o sidecar terminar (ou o canal fechar).
+            while let Some(event) = rx.recv().await {
+                match event {
+                    CommandEvent::Stdout(line) => {
+                        log_record("info", "backend", &line, None, None, Some("stdout"));
+                        ring_push(&app, "stdout", &line);
+                    }
+                    CommandEvent::Stderr(line) => {
+                        log_record("warn", "backend", &line, None, None, Some("stderr"));
+                        ring_push(&app, "stderr", &line);
+                    }
+                    CommandEvent::Error(err) => {
+                        log_record("error", "backend", &err, None, None, None);
+                        ring_push(&app, "error", &err);
+                    }
+                    CommandEvent::Terminated(payload) => {
+                        let msg = "backend terminated";
+                        log_record("warn", "terminated", msg, payload.code, payload.signal, None);
+                        ring_push(
+                            &app,
+                            "terminated",
+                            &format!(
+                                "backend terminated: code={:?} signal={:?}",
+                                payload.code, payload.signal
+                            ),
+                        );
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            // Encerramento pedido por nos (fechar janela / restart): nao respawna.
+            if app.state::<BackendState>().1.load(Ordering::SeqCst) {
+                log_line("supervisor: encerramento intencional, nao respawna");
+                emit_backend_status(&window, "dead", restarts);
+                return;
+            }
+
+            // Se o backend ficou vivo tempo suficiente, considera estavel e
+            // zera o backoff/janela de falhas antes de reiniciar.
+            if spawned_at.elapsed() >= RESTART_FAILURE_WINDOW {
+                backoff = RESTART_BACKOFF_INITIAL;
+                failures.clear();
+            }
+
+            if !register_failure(&mut failures) {
+                log_line("supervisor: backend em loop de crash, desistindo");
+                // Garante UI visivel mesmo quando desistimos de respawnar.
+                let _ = window.show();
+                emit_backend_status(&window, "dead", restarts);
+                return;
+            }
+
+            restarts += 1;
+            emit_backend_status(&window, "restarting", restarts);
+            notify_user(
+                "ChronosInventory",
+                &format!("Backend caiu e esta reiniciando (tentativa {restarts})."),
+            );
+            sleep(backoff).await;
+            backoff = next_backoff(backoff);
+        }
+    });
+}
+
+/// Diretorio observado pelo watcher de dev: `CHRONOS_WATCH_DIR` tem prioridade,
+/// caindo para `CHRONOS_APP_DIR` quando setado.
+fn dev_watch_dir() -> Option<PathBuf> {
+    for key in ["CHRONOS_WATCH_DIR", "CHRONOS_APP_DIR"] {
+        if let Ok(dir) = std::env::var(key) {
+            if !dir.trim().is_empty() {
+                return Some(PathBuf::from(dir));
+            }
+        }
+    }
+    None
+}
+
+/// Mata o sidecar atual sem marcar encerramento intencional, de modo que o
+/// supervisor o respawne automaticamente reusando o health-check. Usado pelo
+/// hot-reload de desenvolvimento.
+fn hot_restart_backend(app: &tauri::AppHandle, window: &tauri::Window) {
+    emit_backend_status(window, "restarting", 0);
+    let port = app.state::<BackendPort>().0.load(Ordering::SeqCst);
+    stop_backend_child(app, "dev_hot_restart");
+    if port != 0 && !wait_backend_port_release(port, Duration::from_secs(5)) {
+        log_line("dev watcher: porta ainda ocupada apos kill; supervisor seguira mesmo assim");
+    }
+}
+
+/// Watcher opt-in (ligado em debug ou via `CHRONOS_WATCH`) que observa o
+/// diretorio fonte do backend e dispara um hot-restart quando arquivos `.py`
+/// mudam, debouncando uma rajada de saves num unico restart.
+fn maybe_start_dev_watcher(app: tauri::AppHandle, window: tauri::Window) {
+    let enabled = cfg!(debug_assertions)
+        || std::env::var("CHRONOS_WATCH")
+            .map(|v| {
+                let v = v.trim();
+                !v.is_empty() && v != "0"
+            })
+            .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let watch_dir = match dev_watch_dir() {
+        Some(dir) => dir,
+        None => {
+            log_line("dev watcher: nenhum diretorio para observar (defina CHRONOS_WATCH_DIR ou CHRONOS_APP_DIR)");
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        use notify::{RecursiveMode, Watcher};
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        }) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                log_line(&format!("dev watcher: falha ao criar watcher: {err}"));
+                return;
+            }
+        };
+        if let Err(err) = watcher.watch(&watch_dir, RecursiveMode::Recursive) {
+            log_line(&format!("dev watcher: falha ao observar {}: {err}", watch_dir.display()));
+            return;
+        }
+        log_line(&format!("dev watcher observando {}", watch_dir.display()));
+
+        while let Ok(first) = rx.recv() {
+            match first {
+                Ok(event) if event_touches_python(&event) => {}
+                Ok(_) => continue,
+                Err(err) => {
+                    log_line(&format!("dev watcher: erro de evento: {err}"));
+                    continue;
+                }
+            }
+            // Debounce: consome a rajada ate um intervalo de silencio.
+            while rx.recv_timeout(DEV_WATCH_DEBOUNCE).is_ok() {}
+            log_line("dev watcher: mudanca em .py, reiniciando backend");
+            hot_restart_backend(&app, &window);
+        }
+    });
+}
+
+fn event_touches_python(event: &notify::Event) -> bool {
+    event
+        .paths
+        .iter()
+        .any(|p| p.extension().map(|ext| ext == "py").unwrap_or(false))
+}
+
+/// Mata o filho atual (se houver) sem alterar a flag de encerramento.
+fn stop_backend_child(app: &tauri::AppHandle, reason: &str) {
     let child = app.state::<BackendState>().0.lock().unwrap().take();
     if let Some(child) = child {
-        match child.kill() {
-            Ok(_) => log_line(&format!("backend finalizado ({reason})")),
-            Err(err) => log_line(&format!("falha ao finalizar backend ({reason}): {err}")),
+        // Encerramento via `terminate_backend` (no Unix SIGTERM com
+        // escalonamento para SIGKILL; no Windows `taskkill /F` imediato),
+        // depois libera o handle do tauri. Como `terminate_backend` ja matou o
+        // processo, o `kill()` costuma falhar contra um PID morto no caminho
+        // feliz: isso e esperado, registra em debug e nao como erro.
+        terminate_backend(child.pid(), reason);
+        if let Err(err) = child.kill() {
+            log_record(
+                "debug",
+                "message",
+                &format!("handle do backend ja encerrado ({reason}): {err}"),
+                None,
+                None,
+                None,
+            );
         }
+        log_line(&format!("backend finalizado ({reason})"));
     } else {
         log_line(&format!("backend ja estava encerrado ({reason})"));
     }
 }
 
-fn is_backend_port_free() -> bool {
-    TcpListener::bind(("127.0.0.1", 8000)).is_ok()
+/// Encerramento intencional: marca a flag para o supervisor nao respawnar e
+/// mata o filho.
+fn stop_backend(app: &tauri::AppHandle, reason: &str) {
+    app.state::<BackendState>().1.store(true, Ordering::SeqCst);
+    stop_backend_child(app, reason);
+}
+
+fn is_backend_port_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
 }
 
-fn wait_backend_port_release(timeout: Duration) -> bool {
+fn wait_backend_port_release(port: u16, timeout: Duration) -> bool {
     let started = Instant::now();
     while started.elapsed() < timeout {
-        if is_backend_port_free() {
+        if is_backend_port_free(port) {
             return true;
         }
         std::thread::sleep(Duration::from_millis(120));
     }
-    is_backend_port_free()
+    is_backend_port_free(port)
+}
+
+#[cfg(unix)]
+fn signal_backend(pid: u32, sig: libc::c_int) {
+    // LIMITACAO: a API de sidecar do Tauri nao expoe `pre_exec`, entao o filho
+    // nao e spawnado com setsid/setpgid e nao e lider de grupo. `kill(-pid)` so
+    // renderia ESRCH, portanto sinalizamos apenas o proprio PID. Consequencia:
+    // processos que o sidecar Python porventura fork-e (workers, netos) nao sao
+    // varridos e podem continuar segurando a porta ate morrerem sozinhos. Um
+    // sweep de grupo real depende de setsid no spawn do sidecar.
+    unsafe {
+        libc::kill(pid as libc::pid_t, sig);
+    }
 }
 
-#[cfg(target_os = "windows")]
-fn taskkill_image(image_name: &str) {
+#[cfg(unix)]
+fn backend_alive(pid: u32) -> bool {
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+/// Termina o sidecar de forma portavel: no Unix manda SIGTERM e escala para
+/// SIGKILL apos o periodo de graca.
+#[cfg(unix)]
+fn terminate_backend(pid: u32, reason: &str) {
+    log_line(&format!("SIGTERM ao backend pid={pid} ({reason})"));
+    signal_backend(pid, libc::SIGTERM);
+    let started = Instant::now();
+    let grace = backend_grace_period();
+    while started.elapsed() < grace {
+        if !backend_alive(pid) {
+            log_line(&format!("backend encerrou graciosamente ({reason})"));
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    log_line(&format!("graca expirou, SIGKILL ao backend pid={pid} ({reason})"));
+    signal_backend(pid, libc::SIGKILL);
+}
+
+/// No Windows mata somente a nossa arvore de PID (`/T`), nunca a imagem inteira.
+/// O `taskkill /F` e imediato: nao ha fase graciosa nem espera pelo periodo de
+/// graca nesta plataforma.
+#[cfg(windows)]
+fn terminate_backend(pid: u32, reason: &str) {
     match StdCommand::new("taskkill")
-        .args(["/F", "/T", "/IM", image_name])
+        .args(["/F", "/T", "/PID", &pid.to_string()])
         .output()
     {
         Ok(output) => {
             let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
             log_line(&format!(
-                "taskkill {image_name}: code={:?} stdout={} stderr={}",
+                "taskkill /PID {pid} ({reason}): code={:?} stdout={} stderr={}",
                 output.status.code(),
                 if stdout.is_empty() { "-" } else { stdout.as_str() },
                 if stderr.is_empty() { "-" } else { stderr.as_str() }
             ));
         }
-        Err(err) => log_line(&format!("taskkill {image_name} falhou: {err}")),
+        Err(err) => log_line(&format!("taskkill /PID {pid} ({reason}) falhou: {err}")),
     }
 }
 
-#[cfg(target_os = "windows")]
-fn force_kill_backend_processes() {
-    taskkill_image("estoque_backend.exe");
-    taskkill_image("estoque_backend-x86_64-pc-windows-msvc.exe");
+/// Porta efetiva onde o backend escuta (0 enquanto nao iniciado).
+#[tauri::command]
+fn backend_port(state: tauri::State<BackendPort>) -> u16 {
+    state.0.load(Ordering::SeqCst)
 }
 
-#[cfg(not(target_os = "windows"))]
-fn force_kill_backend_processes() {}
+/// Ultimas linhas de evento do backend, da mais antiga para a mais recente.
+#[tauri::command]
+fn get_recent_logs(state: tauri::State<LogRing>) -> Vec<LogRecord> {
+    state.0.lock().unwrap().iter().cloned().collect()
+}
 
 #[tauri::command]
 fn restart_app(app: tauri::AppHandle) -> Result<(), String> {
     log_line("restart_app command invoked");
+    let port = app.state::<BackendPort>().0.load(Ordering::SeqCst);
     stop_backend(&app, "restart_app_command");
 
-    if !wait_backend_port_release(Duration::from_secs(3)) {
-        log_line("porta 8000 ainda ocupada apos stop_backend");
-        force_kill_backend_processes();
-        std::thread::sleep(Duration::from_millis(300));
-    }
-
-    if !wait_backend_port_release(Duration::from_secs(3)) {
-        log_line("porta 8000 ainda ocupada antes do restart; prosseguindo mesmo assim");
+    // `stop_backend` ja forcou o encerramento (SIGKILL apos a graca no Unix,
+    // `taskkill /F` imediato no Windows), entao nao
+    // re-sinalizamos o PID armazenado aqui: apos o wait ele pode ter sido
+    // reaproveitado pelo SO (Unix) e atingiriamos um processo sem relacao.
+    if port != 0 && !wait_backend_port_release(port, Duration::from_secs(3)) {
+        log_line(&format!("porta {port} ainda ocupada apos stop_backend; prosseguindo mesmo assim"));
     }
 
     app.restart();
@@ -220,8 +741,10 @@ fn main() {
     }));
 
     let app = tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![restart_app])
-        .manage(BackendState(Mutex::new(None)))
+        .invoke_handler(tauri::generate_handler![restart_app, backend_port, get_recent_logs])
+        .manage(BackendState(Mutex::new(None), AtomicBool::new(false)))
+        .manage(BackendPort(AtomicU16::new(0)))
+        .manage(LogRing(Mutex::new(VecDeque::with_capacity(LOG_RING_CAPACITY))))
         .setup(|app| {
             log_line("App setup iniciado");
             log_startup_paths(app);
@@ -235,46 +758,8 @@ fn main() {
 
             window.hide()?;
 
-            match spawn_backend() {
-                Ok((child, mut rx)) => {
-                    log_line("Backend iniciado");
-                    *app.state::<BackendState>().0.lock().unwrap() = Some(child);
-                    tauri::async_runtime::spawn(async move {
-                        while let Some(event) = rx.recv().await {
-                            match event {
-                                CommandEvent::Stdout(line) => {
-                                    log_line(&format!("backend stdout: {line}"));
-                                }
-                                CommandEvent::Stderr(line) => {
-                                    log_line(&format!("backend stderr: {line}"));
-                                }
-                                CommandEvent::Error(err) => {
-                                    log_line(&format!("backend error: {err}"));
-                                }
-                                CommandEvent::Terminated(payload) => {
-                                    log_line(&format!(
-                                        "backend terminated: code={:?} signal={:?}",
-                                        payload.code, payload.signal
-                                    ));
-                                }
-                                _ => {}
-                            }
-                        }
-                    });
-                }
-                Err(err) => {
-                    log_line(&format!("Falha ao iniciar backend: {err}"));
-                }
-            }
-
-            let window_clone = window.clone();
-            tauri::async_runtime::spawn(async move {
-                let ok = wait_for_health("http://127.0.0.1:8000/health", Duration::from_secs(20)).await;
-                if !ok {
-                    log_line("Backend healthcheck falhou. Mostrando UI mesmo assim.");
-                }
-                let _ = window_clone.show();
-            });
+            start_backend_supervisor(app.handle(), window.clone());
+            maybe_start_dev_watcher(app.handle(), window);
 
             Ok(())
         })